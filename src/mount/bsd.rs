@@ -2,15 +2,18 @@
 use crate::Error;
 use crate::{Errno, NixPath, Result};
 use libc::c_int;
+#[cfg(any(target_os = "freebsd", target_os = "macos"))]
+use libc::c_void;
 #[cfg(target_os = "freebsd")]
-use libc::{c_char, c_uint, c_void};
-#[cfg(target_os = "freebsd")]
+use libc::{c_char, c_uint};
+#[cfg(any(target_os = "freebsd", target_os = "macos"))]
 use std::{
     borrow::Cow,
     ffi::{CStr, CString},
-    fmt, io,
-    marker::PhantomData,
+    fmt,
 };
+#[cfg(target_os = "freebsd")]
+use std::{ffi::OsStr, io, marker::PhantomData, os::unix::io::RawFd};
 
 libc_bitflags!(
     /// Used with [`Nmount::nmount`].
@@ -141,6 +144,87 @@ impl From<NmountError> for io::Error {
 #[cfg(target_os = "freebsd")]
 pub type NmountResult = std::result::Result<(), NmountError>;
 
+/// A boolean `mount(8)`-style option, as understood by
+/// [`Nmount::from_options`].
+#[cfg(target_os = "freebsd")]
+struct MntOpt {
+    /// The option's name, as it appears in a `-o` string.
+    name: &'static str,
+    /// The flag that this option controls.
+    flag: MntFlags,
+    /// Whether the bare form of `name` sets `flag` (as opposed to clearing
+    /// it).  The `no`-prefixed form always does the opposite.
+    sets: bool,
+}
+
+/// The table used by [`Nmount::from_options`] to translate `getmntopts(3)`-style
+/// option names into [`MntFlags`] bits.
+///
+/// This mirrors the table built into `mount(8)`'s `getmntopts()`.
+#[cfg(target_os = "freebsd")]
+const MNT_OPTS: &[MntOpt] = &[
+    MntOpt {
+        name: "ro",
+        flag: MntFlags::MNT_RDONLY,
+        sets: true,
+    },
+    MntOpt {
+        name: "rdonly",
+        flag: MntFlags::MNT_RDONLY,
+        sets: true,
+    },
+    MntOpt {
+        name: "exec",
+        flag: MntFlags::MNT_NOEXEC,
+        sets: false,
+    },
+    MntOpt {
+        name: "suid",
+        flag: MntFlags::MNT_NOSUID,
+        sets: false,
+    },
+    MntOpt {
+        name: "atime",
+        flag: MntFlags::MNT_NOATIME,
+        sets: false,
+    },
+    MntOpt {
+        name: "async",
+        flag: MntFlags::MNT_ASYNC,
+        sets: true,
+    },
+    MntOpt {
+        name: "sync",
+        flag: MntFlags::MNT_SYNCHRONOUS,
+        sets: true,
+    },
+    MntOpt {
+        name: "force",
+        flag: MntFlags::MNT_FORCE,
+        sets: true,
+    },
+    MntOpt {
+        name: "union",
+        flag: MntFlags::MNT_UNION,
+        sets: true,
+    },
+    MntOpt {
+        name: "multilabel",
+        flag: MntFlags::MNT_MULTILABEL,
+        sets: true,
+    },
+    MntOpt {
+        name: "acls",
+        flag: MntFlags::MNT_ACLS,
+        sets: true,
+    },
+    MntOpt {
+        name: "nfsv4acls",
+        flag: MntFlags::MNT_NFS4ACLS,
+        sets: true,
+    },
+];
+
 /// Mount a FreeBSD file system.
 ///
 /// The `nmount(2)` system call works similarly to the `mount(8)` program; it
@@ -202,12 +286,7 @@ impl<'a> Nmount<'a> {
     }
 
     /// Helper function to push a pointer and its length onto the `iov` array.
-    fn push_pointer_and_length(
-        &mut self,
-        val: *const u8,
-        len: usize,
-        is_owned: bool,
-    ) {
+    fn push_pointer_and_length(&mut self, val: *const u8, len: usize, is_owned: bool) {
         self.iov.push(libc::iovec {
             iov_base: val as *mut _,
             iov_len: len,
@@ -226,6 +305,14 @@ impl<'a> Nmount<'a> {
         .unwrap();
     }
 
+    /// Helper function to push an owned `CString` onto the `iov` array.
+    fn push_owned_cstring(&mut self, val: CString) {
+        let len = val.as_bytes_with_nul().len();
+        let ptr = val.into_raw() as *const u8;
+
+        self.push_pointer_and_length(ptr, len, true);
+    }
+
     /// Add an opaque mount option.
     ///
     /// Some file systems take binary-valued mount options.  They can be set
@@ -297,10 +384,7 @@ impl<'a> Nmount<'a> {
     /// let mut nmount: Nmount<'static> = Nmount::new();
     /// nmount.null_opt_owned(read_only);
     /// ```
-    pub fn null_opt_owned<P: ?Sized + NixPath>(
-        &mut self,
-        name: &P,
-    ) -> &mut Self {
+    pub fn null_opt_owned<P: ?Sized + NixPath>(&mut self, name: &P) -> &mut Self {
         self.push_nix_path(name);
         self.push_slice(&[], false);
         self
@@ -349,11 +433,133 @@ impl<'a> Nmount<'a> {
         self
     }
 
+    /// Add a mount option as an open file descriptor.
+    ///
+    /// This is useful for file systems such as `fusefs` that take an
+    /// already-open file descriptor (e.g. to `/dev/fuse`) as a numeric
+    /// option, and is a safe alternative to [`Nmount::mut_ptr_opt`] for that
+    /// case.  Like [`Nmount::i32_opt`], the value is serialized to its
+    /// decimal string form.
+    ///
+    /// # Examples
+    /// ```
+    /// use nix::mount::Nmount;
+    /// use std::os::unix::io::RawFd;
+    ///
+    /// let fd: RawFd = 3;
+    /// Nmount::new().fd_opt("fd", fd);
+    /// ```
+    pub fn fd_opt<P>(&mut self, name: &P, fd: RawFd) -> &mut Self
+    where
+        P: ?Sized + NixPath,
+    {
+        self.i32_opt(name, fd)
+    }
+
+    /// Add a mount option as a 32-bit signed integer, e.g. `fusefs`'s
+    /// `subtype` option.
+    ///
+    /// The value is serialized to its decimal string form, which is the
+    /// wire format `nmount(2)` expects for numeric options.
+    ///
+    /// This has higher runtime cost than [`Nmount::str_opt`], but is useful
+    /// for numeric options that would otherwise require the `unsafe`
+    /// [`Nmount::mut_ptr_opt`].
+    ///
+    /// # Examples
+    /// ```
+    /// use nix::mount::Nmount;
+    ///
+    /// Nmount::new().i32_opt("subtype", 0);
+    /// ```
+    pub fn i32_opt<P>(&mut self, name: &P, val: i32) -> &mut Self
+    where
+        P: ?Sized + NixPath,
+    {
+        self.push_nix_path(name);
+        self.push_owned_cstring(CString::new(val.to_string()).unwrap());
+        self
+    }
+
+    /// Add a mount option as an unsigned 64-bit integer, e.g. `fusefs`'s
+    /// `max_read` option.
+    ///
+    /// The value is serialized to its decimal string form, which is the
+    /// wire format `nmount(2)` expects for numeric options.
+    ///
+    /// This has higher runtime cost than [`Nmount::str_opt`], but is useful
+    /// for numeric options that would otherwise require the `unsafe`
+    /// [`Nmount::mut_ptr_opt`].
+    ///
+    /// # Examples
+    /// ```
+    /// use nix::mount::Nmount;
+    ///
+    /// Nmount::new().u64_opt("max_read", 4096);
+    /// ```
+    pub fn u64_opt<P>(&mut self, name: &P, val: u64) -> &mut Self
+    where
+        P: ?Sized + NixPath,
+    {
+        self.push_nix_path(name);
+        self.push_owned_cstring(CString::new(val.to_string()).unwrap());
+        self
+    }
+
     /// Create a new `Nmount` struct with no options
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Build an [`MntFlags`]/[`Nmount`] pair from a `mount(8)`-style,
+    /// comma-separated option string, e.g. `"ro,noexec,target=/foo"`.
+    ///
+    /// This works like `getmntopts()` in `mount(8)`'s `getmntopts.c`: known
+    /// boolean option names (optionally `no`-prefixed to invert them, as in
+    /// `noatime` vs. `atime`) are folded into the returned [`MntFlags`].
+    /// Options of the form `name=value`, as well as bare names that aren't
+    /// recognized as boolean flags, are passed through unchanged as
+    /// [`Nmount::str_opt_owned`] pairs so that `nmount(2)` can reject or
+    /// accept them itself.
+    ///
+    /// Returns [`Errno::EINVAL`] if `options` isn't valid UTF-8 or contains
+    /// an empty option.
+    ///
+    /// # Examples
+    /// ```
+    /// use nix::mount::{MntFlags, Nmount};
+    ///
+    /// let (flags, mut nmount) =
+    ///     Nmount::from_options("ro,noexec,async,nfsv4acls,target=/foo")
+    ///         .unwrap();
+    /// assert!(flags.contains(MntFlags::MNT_RDONLY));
+    /// assert!(flags.contains(MntFlags::MNT_NOEXEC));
+    /// ```
+    pub fn from_options<S: AsRef<OsStr> + ?Sized>(options: &S) -> Result<(MntFlags, Self)> {
+        let options = options.as_ref().to_str().ok_or(Errno::EINVAL)?;
+        let mut flags = MntFlags::empty();
+        let mut nmount = Self::new();
+        for token in options.split(',') {
+            if token.is_empty() {
+                return Err(Errno::EINVAL);
+            }
+            if let Some((name, value)) = token.split_once('=') {
+                nmount.str_opt_owned(name, value);
+                continue;
+            }
+            let negate = token.len() > 2 && token.starts_with("no");
+            let bare = if negate { &token[2..] } else { token };
+            match MNT_OPTS.iter().find(|opt| opt.name == bare) {
+                Some(opt) if opt.sets ^ negate => flags.insert(opt.flag),
+                Some(opt) => flags.remove(opt.flag),
+                None => {
+                    nmount.str_opt_owned(token, "");
+                }
+            }
+        }
+        Ok((flags, nmount))
+    }
+
     /// Actually mount the file system.
     pub fn nmount(&mut self, flags: MntFlags) -> NmountResult {
         const ERRMSG_NAME: &[u8] = b"errmsg\0";
@@ -402,6 +608,79 @@ impl<'a> Drop for Nmount<'a> {
     }
 }
 
+#[cfg(all(test, target_os = "freebsd"))]
+mod test {
+    use super::*;
+
+    /// Decode the name/value pairs that `from_options` pushed onto
+    /// `nmount`'s `iov`.  Flag-only options are folded into the returned
+    /// `MntFlags` and never pushed, so they do not appear here.
+    fn opt_pairs(nmount: &Nmount) -> Vec<(String, String)> {
+        let to_string = |iov: &libc::iovec| unsafe {
+            CStr::from_ptr(iov.iov_base as *const c_char)
+                .to_string_lossy()
+                .into_owned()
+        };
+        nmount
+            .iov
+            .chunks(2)
+            .map(|pair| (to_string(&pair[0]), to_string(&pair[1])))
+            .collect()
+    }
+
+    #[test]
+    fn from_options_noatime_sets_flag() {
+        let (flags, _) = Nmount::from_options("noatime").unwrap();
+        assert!(flags.contains(MntFlags::MNT_NOATIME));
+    }
+
+    #[test]
+    fn from_options_atime_clears_flag() {
+        let (flags, _) = Nmount::from_options("atime").unwrap();
+        assert!(!flags.contains(MntFlags::MNT_NOATIME));
+    }
+
+    #[test]
+    fn from_options_noexec_sets_flag() {
+        let (flags, _) = Nmount::from_options("noexec").unwrap();
+        assert!(flags.contains(MntFlags::MNT_NOEXEC));
+    }
+
+    #[test]
+    fn from_options_exec_clears_flag() {
+        let (flags, _) = Nmount::from_options("exec").unwrap();
+        assert!(!flags.contains(MntFlags::MNT_NOEXEC));
+    }
+
+    #[test]
+    fn from_options_keyed_option_passes_through() {
+        let (_, nmount) = Nmount::from_options("target=/foo").unwrap();
+        assert_eq!(
+            opt_pairs(&nmount),
+            vec![("target".to_string(), "/foo".to_string())]
+        );
+    }
+
+    #[test]
+    fn from_options_unknown_bare_name_passes_through() {
+        let (_, nmount) = Nmount::from_options("frobnicate").unwrap();
+        assert_eq!(
+            opt_pairs(&nmount),
+            vec![("frobnicate".to_string(), String::new())]
+        );
+    }
+
+    #[test]
+    fn from_options_rejects_empty_string() {
+        assert_eq!(Nmount::from_options("").unwrap_err(), Errno::EINVAL);
+    }
+
+    #[test]
+    fn from_options_rejects_empty_token() {
+        assert_eq!(Nmount::from_options("ro,,x").unwrap_err(), Errno::EINVAL);
+    }
+}
+
 /// Unmount the file system mounted at `mountpoint`.
 ///
 /// Useful flags include
@@ -422,9 +701,156 @@ pub fn unmount<P>(mountpoint: &P, flags: MntFlags) -> Result<()>
 where
     P: ?Sized + NixPath,
 {
-    let res = mountpoint.with_nix_path(|cstr| unsafe {
-        libc::unmount(cstr.as_ptr(), flags.bits())
+    let res =
+        mountpoint.with_nix_path(|cstr| unsafe { libc::unmount(cstr.as_ptr(), flags.bits()) })?;
+
+    Errno::result(res).map(drop)
+}
+
+/// Mount a file system using the classic BSD `mount(2)` system call.
+///
+/// macOS has no `nmount(2)`; instead `mount(2)` takes the file system type
+/// as a name, the target path, a set of [`MntFlags`], and an opaque,
+/// file-system-specific `data` argument in place of [`Nmount`]'s name/value
+/// option list.
+///
+/// # Safety
+///
+/// `data` must be null, or point to a valid instance of whatever argument
+/// structure `fstype` expects, for the duration of the call.
+///
+/// # Examples
+/// ```no_run
+/// use nix::mount::{mount, MntFlags};
+/// use std::ptr;
+///
+/// unsafe {
+///     mount("nullfs", "/mnt", MntFlags::empty(), ptr::null_mut()).unwrap();
+/// }
+/// ```
+#[cfg(target_os = "macos")]
+pub unsafe fn mount<P>(fstype: &str, target: &P, flags: MntFlags, data: *mut c_void) -> Result<()>
+where
+    P: ?Sized + NixPath,
+{
+    let fstype = CString::new(fstype).map_err(|_| Errno::EINVAL)?;
+    let res = target.with_nix_path(|target| unsafe {
+        libc::mount(fstype.as_ptr(), target.as_ptr(), flags.bits(), data)
     })?;
 
     Errno::result(res).map(drop)
 }
+
+#[cfg(any(target_os = "freebsd", target_os = "macos"))]
+impl MntFlags {
+    /// Construct `MntFlags` from the raw `f_flags` field of a `statfs`
+    /// structure, such as the one returned by [`getmntinfo`] or
+    /// [`statfs`](crate::sys::statfs::statfs).
+    pub fn from_statfs_flags(flags: u64) -> Self {
+        Self::from_bits_truncate(flags as c_int)
+    }
+}
+
+/// Information about a single mounted file system, as returned by
+/// [`getmntinfo`].
+#[cfg(any(target_os = "freebsd", target_os = "macos"))]
+#[derive(Clone, Copy)]
+pub struct MountInfo(libc::statfs);
+
+#[cfg(any(target_os = "freebsd", target_os = "macos"))]
+impl MountInfo {
+    /// The name of the file system type, e.g. `"ufs"` or `"nullfs"`.
+    pub fn filesystem_type(&self) -> Cow<str> {
+        unsafe { CStr::from_ptr(self.0.f_fstypename.as_ptr()) }.to_string_lossy()
+    }
+
+    /// The mounted-from device or source, e.g. `"/dev/ada0p2"`.
+    pub fn mounted_from(&self) -> Cow<str> {
+        unsafe { CStr::from_ptr(self.0.f_mntfromname.as_ptr()) }.to_string_lossy()
+    }
+
+    /// The path at which the file system is mounted.
+    pub fn mount_point(&self) -> Cow<str> {
+        unsafe { CStr::from_ptr(self.0.f_mntonname.as_ptr()) }.to_string_lossy()
+    }
+
+    /// The file system ID.
+    ///
+    /// On FreeBSD, use [`MountInfo::fsid_path`] to turn this into the
+    /// `"FSID:val0:val1"` string that [`unmount`] expects when passed
+    /// [`MntFlags::MNT_BYFSID`].
+    pub fn fsid(&self) -> libc::fsid_t {
+        self.0.f_fsid
+    }
+
+    /// The flags that are currently active on this mount.
+    pub fn flags(&self) -> MntFlags {
+        MntFlags::from_statfs_flags(self.0.f_flags as u64)
+    }
+}
+
+#[cfg(target_os = "freebsd")]
+impl MountInfo {
+    /// Format [`MountInfo::fsid`] as the `"FSID:val0:val1"` string that
+    /// [`unmount`] expects when passed [`MntFlags::MNT_BYFSID`].
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use nix::mount::{getmntinfo, unmount, MntFlags};
+    ///
+    /// for mnt in getmntinfo().unwrap() {
+    ///     let _ = unmount(mnt.fsid_path().as_str(), MntFlags::MNT_BYFSID);
+    /// }
+    /// ```
+    pub fn fsid_path(&self) -> String {
+        let fsid = self.fsid();
+        format!("FSID:{}:{}", fsid.val[0], fsid.val[1])
+    }
+}
+
+#[cfg(any(target_os = "freebsd", target_os = "macos"))]
+impl fmt::Debug for MountInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MountInfo")
+            .field("filesystem_type", &self.filesystem_type())
+            .field("mounted_from", &self.mounted_from())
+            .field("mount_point", &self.mount_point())
+            .field("flags", &self.flags())
+            .finish()
+    }
+}
+
+/// Return information about every currently mounted file system.
+///
+/// This wraps [`getmntinfo(3)`](https://www.freebsd.org/cgi/man.cgi?query=getmntinfo).
+/// Note that `getmntinfo(3)` fills in a static buffer that it owns, so
+/// concurrent calls from multiple threads are not safe; this function
+/// copies the data out before returning, but the underlying libc call
+/// itself may race with other threads calling it at the same time.
+///
+/// # Examples
+/// ```
+/// use nix::mount::getmntinfo;
+///
+/// for mnt in getmntinfo().unwrap() {
+///     println!(
+///         "{} on {} type {}",
+///         mnt.mounted_from(),
+///         mnt.mount_point(),
+///         mnt.filesystem_type()
+///     );
+/// }
+/// ```
+#[cfg(any(target_os = "freebsd", target_os = "macos"))]
+pub fn getmntinfo() -> Result<Vec<MountInfo>> {
+    let mut mntbuf: *mut libc::statfs = std::ptr::null_mut();
+    // Unlike most syscalls, getmntinfo(3) signals failure by returning 0
+    // (and setting errno) rather than -1, so Errno::result() can't be used
+    // here.
+    let n = unsafe { libc::getmntinfo(&mut mntbuf, libc::MNT_WAIT) };
+    if n == 0 {
+        return Err(Errno::last());
+    }
+    let stats = unsafe { std::slice::from_raw_parts(mntbuf, n as usize) };
+    Ok(stats.iter().copied().map(MountInfo).collect())
+}